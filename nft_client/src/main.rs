@@ -1,23 +1,90 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 use namada_sdk::{
-    Account, Client, NamadaClient, Transaction, Address, Token,
+    Account, Client, NamadaClient, Address, Token,
 };
 use namada_core::{
     hash::Hash,
     token::Amount,
 };
+use borsh::{BorshSerialize, BorshDeserialize};
 // Assume these types are defined in your NFT module.
 use nft_module::{
     NftMetadata, 
     RoyaltyConfig, 
-    NftAction, 
-    NftToken, 
-    TokenType, 
-    PrivacyConfig, 
+    NftAction,
+    NftToken,
+    PrivacyConfig,
     VisibilityLevel,
+    shielded_binding,
 };
 
+#[cfg(test)]
+mod tests;
+
+/// Fee parameters attached to a transaction.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct FeeParams {
+    /// Token the fee is paid in (`None` = native token).
+    pub token: Option<Address>,
+    /// Fee amount.
+    pub amount: Amount,
+    /// Gas limit.
+    pub gas_limit: u64,
+}
+
+/// A transaction ready to be signed, carrying everything a cold signer needs
+/// without any further chain access. Borsh-serializable so it can be ferried
+/// to an air-gapped machine.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct UnsignedTx {
+    /// The NFT action to execute.
+    pub action: NftAction,
+    /// Account nonce at build time.
+    pub nonce: u64,
+    /// Target chain id.
+    pub chain_id: String,
+    /// Fee parameters.
+    pub fee: FeeParams,
+}
+
+impl UnsignedTx {
+    /// Serializes the transaction into the portable blob that gets signed.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        borsh::to_vec(self).expect("unsigned transaction is serializable")
+    }
+}
+
+/// A signed transaction ready to broadcast.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct SignedTx {
+    /// The transaction that was signed.
+    pub unsigned: UnsignedTx,
+    /// Public key of the signer.
+    pub public_key: Vec<u8>,
+    /// Signature over `unsigned.to_bytes()`.
+    pub signature: Vec<u8>,
+}
+
+/// Signs `unsigned` with `wallet` without requiring any RPC connection, so the
+/// operation can run on an air-gapped machine.
+pub fn sign_offline(unsigned: UnsignedTx, wallet: &Account) -> SignedTx {
+    let signature = wallet.sign(&unsigned.to_bytes());
+    SignedTx {
+        public_key: wallet.public_key(),
+        unsigned,
+        signature,
+    }
+}
+
+/// Derives a note component (commitment or nullifier) by hashing key material
+/// together with the token id.
+fn note_hash(key: &[u8], token_id: &Hash) -> Hash {
+    let mut preimage = key.to_vec();
+    preimage.extend_from_slice(token_id.as_ref());
+    Hash::sha256(&preimage)
+}
+
 /// NFT Minting Client for Namada.
 pub struct NftMintClient {
     client: NamadaClient,
@@ -35,41 +102,100 @@ impl NftMintClient {
         Ok(Self { client, wallet })
     }
     
-    /// Mints a new NFT by building, signing, and submitting the minting transaction.
-    pub async fn mint_nft(
-        &self, 
+    /// Builds an unsigned minting transaction, fetching the nonce and chain id
+    /// and attaching the default fee params, so it can be signed offline.
+    pub async fn build_mint_tx(
+        &self,
         collection_address: Address,
         metadata: NftMetadata,
         royalty_config: Option<RoyaltyConfig>,
-    ) -> Result<Hash, Box<dyn std::error::Error>> {
-        // Build the NFT minting transaction.
-        let tx = Transaction::new()
-            .with_action(NftAction::Mint {
-                collection: collection_address,
-                metadata: metadata.clone(),
-                royalty_config,
-            })
-            .sign(&self.wallet);
-        
-        // Submit the transaction and await the receipt.
-        let tx_hash = self.client.submit_transaction(tx).await?;
+    ) -> Result<UnsignedTx, Box<dyn std::error::Error>> {
+        self.build_tx(NftAction::Mint {
+            collection: collection_address,
+            metadata,
+            royalty_config,
+        }).await
+    }
+
+    /// Builds an unsigned transfer transaction, fetching the chain context
+    /// needed to sign it offline.
+    pub async fn build_transfer_tx(
+        &self,
+        token_id: Hash,
+        recipient: Address,
+        sale_price: Option<Amount>,
+    ) -> Result<UnsignedTx, Box<dyn std::error::Error>> {
+        self.build_tx(NftAction::Transfer {
+            token_id,
+            recipient,
+            sale_price,
+        }).await
+    }
+
+    /// Wraps an action together with the current chain context into an
+    /// [`UnsignedTx`].
+    async fn build_tx(&self, action: NftAction) -> Result<UnsignedTx, Box<dyn std::error::Error>> {
+        let nonce = self.client.query_nonce(self.wallet.address()).await?;
+        let chain_id = self.client.chain_id().await?;
+        Ok(UnsignedTx {
+            action,
+            nonce,
+            chain_id,
+            fee: self.default_fee(),
+        })
+    }
+
+    /// Default fee parameters for NFT transactions.
+    fn default_fee(&self) -> FeeParams {
+        FeeParams { token: None, amount: Amount::from(1_000u64), gas_limit: 50_000 }
+    }
+
+    /// Broadcasts a signed transaction and waits for its receipt.
+    pub async fn submit(&self, signed: SignedTx) -> Result<Hash, Box<dyn std::error::Error>> {
+        let tx_hash = self.client.broadcast_tx(borsh::to_vec(&signed)?).await?;
         let receipt = self.client.wait_for_tx(tx_hash).await?;
-        
         if receipt.status.is_success() {
             Ok(tx_hash)
         } else {
-            Err("NFT minting transaction failed".into())
+            Err("transaction failed".into())
         }
     }
+
+    /// Mints a new NFT by building, signing, and submitting the minting transaction.
+    pub async fn mint_nft(
+        &self,
+        collection_address: Address,
+        metadata: NftMetadata,
+        royalty_config: Option<RoyaltyConfig>,
+    ) -> Result<Hash, Box<dyn std::error::Error>> {
+        let unsigned = self.build_mint_tx(collection_address, metadata, royalty_config).await?;
+        let signed = sign_offline(unsigned, &self.wallet);
+        self.submit(signed).await
+    }
     
-    /// Retrieves the list of NFT tokens associated with the client's wallet.
-    pub async fn get_wallet_nfts(&self) -> Result<Vec<NftToken>, Box<dyn std::error::Error>> {
-        let nft_tokens: Vec<NftToken> = self.client
-            .query_account_tokens(self.wallet.address())
-            .into_iter()
-            .filter(|token| token.token_type == TokenType::Nft)
-            .collect();
-        Ok(nft_tokens)
+    /// Retrieves the wallet's NFTs in a collection using the contract's owner
+    /// enumeration, paging through holdings instead of scanning every account
+    /// token.
+    pub async fn get_wallet_nfts(
+        &self,
+        collection: Address,
+    ) -> Result<Vec<NftToken>, Box<dyn std::error::Error>> {
+        const PAGE: usize = 100;
+        let owner = self.wallet.address();
+        let mut start = 0usize;
+        let mut tokens = Vec::new();
+        loop {
+            let page = self.client
+                .query_tokens_for_owner(collection.clone(), owner.clone(), start, PAGE)
+                .await?;
+            let fetched = page.len();
+            tokens.extend(page);
+            if fetched < PAGE {
+                break;
+            }
+            start += PAGE;
+        }
+        Ok(tokens)
     }
     
     /// Transfers an NFT to a recipient.
@@ -79,22 +205,35 @@ impl NftMintClient {
         recipient: Address,
         sale_price: Option<Amount>,
     ) -> Result<Hash, Box<dyn std::error::Error>> {
-        let tx = Transaction::new()
-            .with_action(NftAction::Transfer {
-                token_id,
-                recipient,
-                sale_price,
-            })
-            .sign(&self.wallet);
-        
-        let tx_hash = self.client.submit_transaction(tx).await?;
-        let receipt = self.client.wait_for_tx(tx_hash).await?;
-        
-        if receipt.status.is_success() {
-            Ok(tx_hash)
-        } else {
-            Err("NFT transfer transaction failed".into())
-        }
+        let unsigned = self.build_transfer_tx(token_id, recipient, sale_price).await?;
+        let signed = sign_offline(unsigned, &self.wallet);
+        self.submit(signed).await
+    }
+
+    /// Transfers an NFT into the shielded pool. The nullifier is derived from
+    /// the wallet's spending key and the note commitment from
+    /// `recipient_viewing_key`; the proof is [`shielded_binding`] over those
+    /// public values and carries no key material.
+    pub async fn shielded_transfer(
+        &self,
+        token_id: Hash,
+        recipient_viewing_key: Vec<u8>,
+    ) -> Result<Hash, Box<dyn std::error::Error>> {
+        // The nullifier is bound to the spending key so re-spending the same
+        // note always reproduces it; the commitment hides the new owner behind
+        // their viewing key.
+        let nullifier = note_hash(&self.wallet.spending_key(), &token_id);
+        let commitment = note_hash(&recipient_viewing_key, &token_id);
+        let proof = shielded_binding(&token_id, &commitment, &nullifier);
+
+        let unsigned = self.build_tx(NftAction::ShieldedTransfer {
+            token_id,
+            commitment,
+            nullifier,
+            proof,
+        }).await?;
+        let signed = sign_offline(unsigned, &self.wallet);
+        self.submit(signed).await
     }
 }
 
@@ -125,6 +264,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             encryption_key: None,
             visibility: VisibilityLevel::Public,
         }),
+        uses: None,
     };
     
     // Optional royalty configuration.
@@ -137,15 +277,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     
     // Mint the NFT.
     let token_id = client.mint_nft(
-        collection_address,
+        collection_address.clone(),
         metadata,
         Some(royalty_config),
     ).await?;
-    
+
     println!("NFT minted successfully. Token ID: {:?}", token_id);
-    
+
     // Retrieve and display wallet NFTs.
-    let wallet_nfts = client.get_wallet_nfts().await?;
+    let wallet_nfts = client.get_wallet_nfts(collection_address).await?;
     for nft in wallet_nfts {
         println!("NFT: {}", nft.name);
         println!("Token ID: {:?}", nft.token_id);
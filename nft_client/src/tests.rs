@@ -7,6 +7,8 @@ mod client_tests {
     use std::collections::HashMap;
     use std::str::FromStr;
     use tokio::runtime::Runtime;
+    use borsh::{BorshSerialize, BorshDeserialize};
+    use crate::*;
 
     // --- Dummy types to simulate NamadaClient behavior ---
 
@@ -133,8 +135,30 @@ mod client_tests {
         async fn get_wallet_nfts(&self) -> Result<Vec<NftToken>, Box<dyn std::error::Error>> {
             Ok(self.client.query_account_tokens(self.wallet.address()))
         }
+
+        /// Builds the intermediate unsigned transaction so tests can assert on
+        /// the serialized payload rather than only the final hash. Produces the
+        /// crate's real [`UnsignedTx`] — fee params and all — so the assertion
+        /// exercises the actual build-stage shape.
+        fn build_mint_tx(
+            &self,
+            collection_address: Address,
+            metadata: NftMetadata,
+            royalty_config: Option<RoyaltyConfig>,
+        ) -> UnsignedTx {
+            UnsignedTx {
+                action: crate::NftAction::Mint {
+                    collection: collection_address,
+                    metadata,
+                    royalty_config,
+                },
+                nonce: 0,
+                chain_id: "namada-test".to_string(),
+                fee: FeeParams { token: None, amount: Amount::from(1_000u64), gas_limit: 50_000 },
+            }
+        }
     }
-    
+
     // --- Dummy implementations for types used by the client ---
     
     /// Minimal dummy Transaction type for testing.
@@ -165,7 +189,7 @@ mod client_tests {
     }
     
     /// Minimal dummy NFT action enumeration.
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
     enum NftAction {
         Mint {
             collection: Address,
@@ -218,6 +242,7 @@ mod client_tests {
                 encryption_key: None,
                 visibility: VisibilityLevel::Public,
             }),
+            uses: None,
         };
         
         // Optional royalty configuration.
@@ -237,6 +262,37 @@ mod client_tests {
         assert_eq!(token_id, Hash::from([1u8; 32]));
     }
     
+    #[test]
+    fn test_build_mint_tx_payload() {
+        let wallet_address = Address::from_str("namada1dummywallet").unwrap();
+        let dummy_account = DummyAccount::new(wallet_address.clone());
+        let test_client = TestNftMintClient::new(DummyNamadaClient, dummy_account);
+
+        let metadata = NftMetadata {
+            token_id: Hash::default(),
+            name: "Test NFT".to_string(),
+            description: None,
+            uri: None,
+            creator: wallet_address.clone(),
+            attributes: HashMap::new(),
+            transferable: true,
+            privacy_config: None,
+            uses: None,
+        };
+        let collection_address = Address::from_str("namada1collection").unwrap();
+
+        // The offline build stage yields a portable, round-trippable blob that
+        // carries the real fee params a cold signer needs.
+        let unsigned = test_client.build_mint_tx(collection_address, metadata, None);
+        assert_eq!(unsigned.chain_id, "namada-test");
+        assert_eq!(unsigned.fee.amount, Amount::from(1_000u64));
+        assert_eq!(unsigned.fee.gas_limit, 50_000);
+
+        let bytes = borsh::to_vec(&unsigned).expect("unsigned tx serializes");
+        let decoded = UnsignedTx::try_from_slice(&bytes).expect("unsigned tx round-trips");
+        assert!(matches!(decoded.action, crate::NftAction::Mint { .. }));
+    }
+
     #[tokio::test]
     async fn test_client_transfer_nft_success() {
         let dummy_client = DummyNamadaClient;
@@ -0,0 +1,1496 @@
+//! On-chain NFT program for Namada.
+//!
+//! The crate exposes the [`NftCollection`] state machine together with the
+//! [`NftAction`] set understood by the transaction runtime. Collections own the
+//! canonical `token_id -> owner` map, the per-token [`NftMetadata`], and the
+//! [`RoyaltyConfig`] used to split secondary-sale proceeds.
+
+use std::collections::{HashMap, HashSet};
+
+pub use namada_core::{
+    address::Address,
+    hash::Hash,
+    token::Amount,
+};
+use borsh::{BorshDeserialize, BorshSerialize};
+
+/// How a token is accounted for on chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum TokenType {
+    /// A non-fungible token managed by this program.
+    Nft,
+    /// Any other token kind (fungible balances, etc.).
+    Other,
+}
+
+/// How widely a token's ownership and metadata may be observed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum VisibilityLevel {
+    /// Ownership and metadata are fully public.
+    Public,
+    /// Ownership is not publicly observable. Tokens in the shielded pool report
+    /// this level and have no public owner; a token outside the pool whose
+    /// [`PrivacyConfig`] sets this level keeps its public owner.
+    Private,
+}
+
+/// Optional privacy settings attached to a token's metadata.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct PrivacyConfig {
+    /// Whether the off-chain payload is encrypted.
+    pub encrypted: bool,
+    /// Symmetric key material, when the holder chooses to publish it.
+    pub encryption_key: Option<Vec<u8>>,
+    /// The visibility level reported by [`NftCollection::visibility`] for a
+    /// token outside the shielded pool. [`NftCollection::public_owner`] still
+    /// returns the owner of such a token when this is `Private`.
+    pub visibility: VisibilityLevel,
+}
+
+/// Metadata describing a single NFT.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct NftMetadata {
+    /// Canonical token identifier. Replaced by [`NftCollection::mint`].
+    pub token_id: Hash,
+    /// Human readable name.
+    pub name: String,
+    /// Optional long-form description.
+    pub description: Option<String>,
+    /// Optional content URI (typically `ipfs://`).
+    pub uri: Option<String>,
+    /// Address credited as the creator.
+    pub creator: Address,
+    /// Free-form trait map.
+    pub attributes: HashMap<String, String>,
+    /// Whether the token may change hands after mint.
+    pub transferable: bool,
+    /// Optional privacy settings.
+    pub privacy_config: Option<PrivacyConfig>,
+    /// Optional usage accounting for redeemable tokens (tickets, passes).
+    pub uses: Option<Uses>,
+}
+
+/// How a usage-limited token is consumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub enum UseMethod {
+    /// The token is burned once its uses are exhausted.
+    Burn,
+    /// The token may be used up to `total` times and then persists.
+    Multiple,
+    /// A single-use token. [`NftCollection::utilize`] limits it by `remaining`
+    /// exactly as for [`UseMethod::Multiple`], so it is usable only once when
+    /// minted with a `total` of 1.
+    Single,
+}
+
+/// Usage accounting attached to a redeemable token.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct Uses {
+    /// How the token is consumed.
+    pub use_method: UseMethod,
+    /// Total uses granted at mint.
+    pub total: u64,
+    /// Uses still available.
+    pub remaining: u64,
+}
+
+/// Royalty split applied on every sale of a token.
+///
+/// Percentages are expressed in basis points (1% = 100).
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct RoyaltyConfig {
+    /// Primary royalty beneficiary.
+    pub creator: Address,
+    /// Creator royalty, in basis points of the sale price.
+    pub royalty_percentage: u16,
+    /// Additional beneficiaries and their basis-point shares.
+    pub secondary_recipients: Vec<(Address, u16)>,
+    /// Token the royalty is denominated in (`None` = native token).
+    pub royalty_token: Option<Address>,
+}
+
+/// A light-weight token view returned by enumeration and wallet queries.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct NftToken {
+    /// Canonical token identifier.
+    pub token_id: Hash,
+    /// Human readable name.
+    pub name: String,
+    /// Accounting kind.
+    pub token_type: TokenType,
+}
+
+/// Execution context threaded through every state transition.
+///
+/// Carries the information the runtime would normally derive from the wrapping
+/// transaction: who signed it and the block it is being applied in.
+#[derive(Debug, Clone, Default)]
+pub struct TxContext {
+    /// The address that authorized the transaction, when known.
+    pub sender: Option<Address>,
+    /// Height of the block the transaction is applied in.
+    pub block_height: u64,
+    /// Unix timestamp of the block the transaction is applied in.
+    pub block_time: u64,
+}
+
+/// The concrete royalty payout produced by a sale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoyaltyPayout {
+    /// Total amount withheld for royalties.
+    pub total: Amount,
+    /// Per-recipient breakdown.
+    pub payments: Vec<(Address, Amount)>,
+}
+
+/// The result of applying a [`NftCollection::transfer`].
+#[derive(Debug, Clone)]
+pub struct TransferOutcome {
+    /// Token that changed hands.
+    pub token_id: Hash,
+    /// Previous owner.
+    pub from: Address,
+    /// New owner.
+    pub to: Address,
+    /// Royalty split, when a sale price was supplied and royalties apply.
+    pub royalties: Option<RoyaltyPayout>,
+    /// Program fee withheld for the collection's fee collector.
+    pub program_fee: Option<Amount>,
+}
+
+/// An active fixed-price listing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Listing {
+    /// Address that listed the token.
+    pub seller: Address,
+    /// Asking price.
+    pub price: Amount,
+    /// Token the price is denominated in (`None` = native token).
+    pub payment_token: Option<Address>,
+}
+
+/// A standing offer on a token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Offer {
+    /// Address that made the offer.
+    pub buyer: Address,
+    /// Offered amount.
+    pub amount: Amount,
+    /// Block time after which the offer expires.
+    pub expiry: u64,
+}
+
+/// A single bid in an auction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bid {
+    /// Address that placed the bid.
+    pub bidder: Address,
+    /// Escrowed bid amount.
+    pub amount: Amount,
+}
+
+/// An open English auction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Auction {
+    /// Address that opened the auction.
+    pub seller: Address,
+    /// Reserve price below which the token will not sell.
+    pub reserve: Amount,
+    /// Block time at which bidding closes.
+    pub end_time: u64,
+    /// Current highest bid, if any.
+    pub highest_bid: Option<Bid>,
+}
+
+/// The settlement produced by a successful sale or auction.
+#[derive(Debug, Clone)]
+pub struct SaleSettlement {
+    /// Token that changed hands.
+    pub token_id: Hash,
+    /// Previous owner.
+    pub seller: Address,
+    /// New owner.
+    pub buyer: Address,
+    /// Sale price.
+    pub price: Amount,
+    /// Royalty split paid to creators and secondary recipients.
+    pub royalties: Option<RoyaltyPayout>,
+    /// Program fee withheld for the fee collector.
+    pub program_fee: Option<Amount>,
+    /// Net proceeds paid to the seller.
+    pub seller_proceeds: Amount,
+    /// Refund returned to a superseded top bidder, when applicable.
+    pub refund: Option<(Address, Amount)>,
+}
+
+/// The escrow state of a pending hash-timelocked swap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapState {
+    /// Address that opened the swap and receives a refund.
+    pub originator: Address,
+    /// Address entitled to claim the token with the preimage.
+    pub counterparty: Address,
+    /// SHA256 hash of the swap secret.
+    pub hashlock: Hash,
+    /// Block time after which the swap may be refunded.
+    pub timelock: u64,
+}
+
+/// An append-only Merkle tree of note commitments for the shielded pool.
+#[derive(Debug, Clone, Default)]
+pub struct NoteCommitmentTree {
+    /// Commitments in insertion order.
+    leaves: Vec<Hash>,
+}
+
+impl NoteCommitmentTree {
+    /// Appends a commitment as a new leaf.
+    pub fn append(&mut self, commitment: Hash) {
+        self.leaves.push(commitment);
+    }
+
+    /// Number of commitments in the tree.
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Whether the tree is empty.
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Computes the current root by folding the leaves pairwise.
+    pub fn root(&self) -> Hash {
+        if self.leaves.is_empty() {
+            return Hash::default();
+        }
+        let mut level: Vec<Hash> = self.leaves.clone();
+        while level.len() > 1 {
+            level = level
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_pair(left, right),
+                    [single] => *single,
+                    _ => unreachable!("chunks(2) yields at most two elements"),
+                })
+                .collect();
+        }
+        level[0]
+    }
+}
+
+/// Errors returned by the NFT program.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum NftError {
+    /// The caller is not permitted to perform the action.
+    #[error("caller is not authorized for this action")]
+    Unauthorized,
+    /// The referenced token does not exist.
+    #[error("token not found")]
+    TokenNotFound,
+    /// The token's metadata marks it as non-transferable.
+    #[error("token is not transferable")]
+    NotTransferable,
+    /// A bridge action was attempted before the bridge was configured.
+    #[error("bridge is not configured for this collection")]
+    BridgeNotConfigured,
+    /// An attestation failed signature or quorum verification.
+    #[error("attestation is invalid")]
+    InvalidAttestation,
+    /// The attestation digest has already been released (replay).
+    #[error("attestation has already been consumed")]
+    AttestationAlreadyConsumed,
+    /// A native release named a token that is not held in bridge custody.
+    #[error("token is not held in bridge custody")]
+    NotInCustody,
+    /// The token carries no usage accounting, so it cannot be redeemed.
+    #[error("token is not redeemable")]
+    NotRedeemable,
+    /// The token has no uses remaining.
+    #[error("token has no uses remaining")]
+    UsesExhausted,
+    /// A delegate attempted to use more than its granted allowance.
+    #[error("use authority allowance exceeded")]
+    UseAuthorityExceeded,
+    /// No active listing or auction exists for the token.
+    #[error("listing not found")]
+    ListingNotFound,
+    /// Withheld royalties and program fee exceed the sale price.
+    #[error("royalties and fee exceed the sale price")]
+    RoyaltiesExceedPrice,
+    /// A bid did not exceed the current highest bid or the reserve.
+    #[error("bid is too low")]
+    BidTooLow,
+    /// An auction was settled before its end time.
+    #[error("auction has not ended")]
+    AuctionNotEnded,
+    /// A bid was placed after the auction's end time.
+    #[error("auction bidding has closed")]
+    AuctionClosed,
+    /// No matching active offer exists for the token.
+    #[error("offer not found")]
+    OfferNotFound,
+    /// The nullifier has already been spent (double-spend).
+    #[error("nullifier has already been used")]
+    NullifierAlreadyUsed,
+    /// A shielded-transfer proof failed verification.
+    #[error("shielded transfer proof is invalid")]
+    InvalidProof,
+    /// A swap preimage did not hash to the swap's hashlock.
+    #[error("swap preimage is invalid")]
+    InvalidPreimage,
+    /// A swap was claimed after its timelock expired.
+    #[error("swap has expired")]
+    SwapExpired,
+    /// A swap was refunded before its timelock expired.
+    #[error("swap has not yet expired")]
+    SwapNotExpired,
+}
+
+/// Actions understood by the NFT program.
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub enum NftAction {
+    /// Mint a new token into a collection.
+    Mint {
+        /// Target collection address.
+        collection: Address,
+        /// Metadata for the new token.
+        metadata: NftMetadata,
+        /// Optional royalty configuration.
+        royalty_config: Option<RoyaltyConfig>,
+    },
+    /// Transfer a token to a new owner, optionally recording a sale price.
+    Transfer {
+        /// Token to move.
+        token_id: Hash,
+        /// New owner.
+        recipient: Address,
+        /// Sale price, used to compute royalties and the program fee.
+        sale_price: Option<Amount>,
+    },
+    /// Escrow a token for transfer to another chain via the guardian bridge.
+    Lock {
+        /// Token to bridge out.
+        token_id: Hash,
+        /// Destination chain id.
+        target_chain: u16,
+        /// Recipient address on the destination chain.
+        target_recipient: Address,
+    },
+    /// Release an escrowed or wrapped token against a guardian attestation.
+    Release {
+        /// The signed attestation produced by the guardian set.
+        vaa: Vaa,
+    },
+    /// Consume `count` uses of a redeemable token.
+    Utilize {
+        /// Token to use.
+        token_id: Hash,
+        /// Number of uses to consume.
+        count: u64,
+    },
+    /// Grant a delegate authority to use a token on the owner's behalf.
+    ApproveUseAuthority {
+        /// Token the authority applies to.
+        token_id: Hash,
+        /// Address granted use authority.
+        delegate: Address,
+        /// Number of uses the delegate may consume.
+        allowed_uses: u64,
+    },
+    /// List a token for sale at a fixed price.
+    List {
+        /// Token to list.
+        token_id: Hash,
+        /// Asking price.
+        price: Amount,
+        /// Token the price is denominated in (`None` = native token).
+        payment_token: Option<Address>,
+    },
+    /// Cancel an active listing.
+    CancelListing {
+        /// Token whose listing is cancelled.
+        token_id: Hash,
+    },
+    /// Buy a listed token at its asking price.
+    Buy {
+        /// Token to buy.
+        token_id: Hash,
+    },
+    /// Make a standing offer on a token.
+    MakeOffer {
+        /// Token the offer targets.
+        token_id: Hash,
+        /// Offered amount.
+        amount: Amount,
+        /// Block time after which the offer expires.
+        expiry: u64,
+    },
+    /// Accept a standing offer on a token, settling it like a sale.
+    AcceptOffer {
+        /// Token whose offer is accepted.
+        token_id: Hash,
+        /// Buyer whose offer is accepted.
+        buyer: Address,
+    },
+    /// Open an English auction for a token.
+    CreateAuction {
+        /// Token to auction.
+        token_id: Hash,
+        /// Reserve price below which the token will not sell.
+        reserve: Amount,
+        /// Block time at which bidding closes.
+        end_time: u64,
+    },
+    /// Place a bid on an open auction.
+    PlaceBid {
+        /// Token being auctioned.
+        token_id: Hash,
+        /// Bid amount.
+        amount: Amount,
+    },
+    /// Settle a closed auction, transferring the token to the top bidder.
+    SettleAuction {
+        /// Token whose auction is settled.
+        token_id: Hash,
+    },
+    /// Escrow a token into a hash-timelocked swap.
+    LockForSwap {
+        /// Token to escrow.
+        token_id: Hash,
+        /// SHA256 hash of the swap secret.
+        hashlock: Hash,
+        /// Block time after which the swap may be refunded.
+        timelock: u64,
+        /// Address entitled to claim the token with the preimage.
+        counterparty: Address,
+    },
+    /// Claim an escrowed token by revealing the swap preimage.
+    ClaimSwap {
+        /// Token to claim.
+        token_id: Hash,
+        /// Preimage `x` such that `SHA256(x) == hashlock`.
+        preimage: Vec<u8>,
+    },
+    /// Refund an escrowed token to its originator after the timelock expires.
+    RefundSwap {
+        /// Token to refund.
+        token_id: Hash,
+    },
+    /// Transfer a token into (or within) the shielded pool.
+    ShieldedTransfer {
+        /// Token being shielded-transferred.
+        token_id: Hash,
+        /// Note commitment recorded into the commitment tree.
+        commitment: Hash,
+        /// Nullifier spent by this transfer.
+        nullifier: Hash,
+        /// Proof binding the public inputs, checked against [`shielded_binding`].
+        proof: Vec<u8>,
+    },
+}
+
+/// A set of guardians authorized to attest cross-chain transfers, together
+/// with the number of signatures required to form a quorum.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct GuardianSet {
+    /// Guardian addresses.
+    pub guardians: Vec<Address>,
+    /// Number of distinct guardian signatures required for a valid attestation.
+    pub quorum: usize,
+}
+
+/// The cross-chain payload emitted when a token is locked and attested for
+/// release on the destination chain.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct TransferPayload {
+    /// Chain the token is native to.
+    pub origin_chain: u16,
+    /// Collection address on the origin chain.
+    pub origin_collection: Address,
+    /// Token id on the origin chain.
+    pub origin_token_id: Hash,
+    /// Metadata carried across the bridge.
+    pub metadata: NftMetadata,
+    /// Royalty configuration preserved across the bridge.
+    pub royalty_config: Option<RoyaltyConfig>,
+    /// Destination chain id.
+    pub target_chain: u16,
+    /// Recipient on the destination chain.
+    pub target_recipient: Address,
+}
+
+impl TransferPayload {
+    /// Returns the digest signed by guardians and used for replay protection.
+    pub fn digest(&self) -> Hash {
+        Hash::sha256(&borsh::to_vec(self).expect("payload is serializable"))
+    }
+}
+
+/// A single guardian's signature over a payload digest.
+///
+/// # Security
+///
+/// This is an **insecure simulation**: the "signature" is a deterministic hash
+/// of `(guardian, digest)` with no secret key, so anyone can forge a full
+/// quorum. A production bridge must verify real guardian signatures (e.g.
+/// Ed25519) here before this gates any custody. The stub preserves the
+/// `attest`/`verifies` shape so the surrounding bridge logic is exercisable.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct GuardianSignature {
+    /// The signing guardian.
+    pub guardian: Address,
+    /// Signature bytes over the payload digest.
+    pub signature: Vec<u8>,
+}
+
+impl GuardianSignature {
+    /// Produces the guardian's attestation over `digest`.
+    pub fn attest(guardian: &Address, digest: &Hash) -> Self {
+        Self {
+            guardian: guardian.clone(),
+            signature: Self::expected(guardian, digest),
+        }
+    }
+
+    /// Verifies the signature binds this guardian to `digest`.
+    fn verifies(&self, digest: &Hash) -> bool {
+        self.signature == Self::expected(&self.guardian, digest)
+    }
+
+    /// The canonical signature bytes for `(guardian, digest)`.
+    ///
+    /// Forgeable by design — see the [`GuardianSignature`] security note.
+    fn expected(guardian: &Address, digest: &Hash) -> Vec<u8> {
+        let mut preimage = guardian.to_string().into_bytes();
+        preimage.extend_from_slice(digest.as_ref());
+        Hash::sha256(&preimage).as_ref().to_vec()
+    }
+}
+
+/// A verified action approval: a transfer payload and the guardian signatures
+/// attesting it.
+#[derive(Debug, Clone, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct Vaa {
+    /// The attested payload.
+    pub payload: TransferPayload,
+    /// Guardian signatures over `payload.digest()`.
+    pub signatures: Vec<GuardianSignature>,
+}
+
+/// A minted NFT collection and all of its on-chain state.
+#[derive(Debug, Clone)]
+pub struct NftCollection {
+    /// Display name of the collection.
+    pub name: String,
+    /// Address that receives the per-sale program fee.
+    pub fee_collector: Address,
+    /// Program fee in basis points of the sale price.
+    pub fee_basis_points: u16,
+    /// Canonical ownership map.
+    pub token_owners: HashMap<Hash, Address>,
+    /// Per-token metadata.
+    pub metadata: HashMap<Hash, NftMetadata>,
+    /// Per-token royalty configuration.
+    pub royalties: HashMap<Hash, RoyaltyConfig>,
+    /// All token ids in mint order, for collection-wide enumeration.
+    token_list: Vec<Hash>,
+    /// Reverse index from owner to the tokens they hold, for owner enumeration.
+    owner_index: HashMap<Address, Vec<Hash>>,
+    /// Bridge configuration, installed on demand.
+    pub bridge: Option<BridgeConfig>,
+    /// Maps a foreign token to the local id that wraps it, keyed by
+    /// `(origin_chain, origin_collection, origin_token_id)`.
+    pub wrapped_registry: HashMap<(u16, Address, Hash), Hash>,
+    /// Reverse lookup from a local wrapped id to its foreign origin.
+    wrapped_origins: HashMap<Hash, (u16, Address, Hash)>,
+    /// Per-delegate use authority, keyed by `(token_id, delegate)`.
+    pub use_authorities: HashMap<(Hash, Address), u64>,
+    /// Active fixed-price listings, keyed by token.
+    pub listings: HashMap<Hash, Listing>,
+    /// Standing offers per token.
+    pub offers: HashMap<Hash, Vec<Offer>>,
+    /// Open auctions, keyed by token.
+    pub auctions: HashMap<Hash, Auction>,
+    /// Commitment tree backing shielded transfers.
+    pub note_tree: NoteCommitmentTree,
+    /// Spent nullifiers, for shielded double-spend protection.
+    nullifiers: HashSet<Hash>,
+    /// Tokens currently held in the shielded pool (owner hidden).
+    shielded: HashSet<Hash>,
+    /// Pending hash-timelocked swaps, keyed by escrowed token.
+    pub swaps: HashMap<Hash, SwapState>,
+    /// Preimages revealed by claimed swaps, keyed by hashlock, so a
+    /// counterparty can reuse the secret to claim the paired asset.
+    pub revealed_secrets: HashMap<Hash, Vec<u8>>,
+    /// Attestation digests already released, for replay protection.
+    consumed_attestations: HashSet<Hash>,
+    /// Monotonic counter feeding deterministic token ids.
+    next_index: u64,
+}
+
+/// Per-collection bridge configuration.
+#[derive(Debug, Clone)]
+pub struct BridgeConfig {
+    /// Id of the chain this collection lives on.
+    pub chain_id: u16,
+    /// This collection's own address, stamped into outbound payloads.
+    pub collection_address: Address,
+    /// Program-controlled custody address holding escrowed native tokens.
+    pub custody: Address,
+    /// Guardian set authorized to attest inbound releases.
+    pub guardians: GuardianSet,
+}
+
+impl NftCollection {
+    /// Creates an empty collection whose program fee is paid to `fee_collector`.
+    pub fn new(name: String, fee_collector: Address, fee_basis_points: u16) -> Self {
+        Self {
+            name,
+            fee_collector,
+            fee_basis_points,
+            token_owners: HashMap::new(),
+            metadata: HashMap::new(),
+            royalties: HashMap::new(),
+            token_list: Vec::new(),
+            owner_index: HashMap::new(),
+            bridge: None,
+            wrapped_registry: HashMap::new(),
+            wrapped_origins: HashMap::new(),
+            use_authorities: HashMap::new(),
+            listings: HashMap::new(),
+            offers: HashMap::new(),
+            auctions: HashMap::new(),
+            note_tree: NoteCommitmentTree::default(),
+            nullifiers: HashSet::new(),
+            shielded: HashSet::new(),
+            swaps: HashMap::new(),
+            revealed_secrets: HashMap::new(),
+            consumed_attestations: HashSet::new(),
+            next_index: 0,
+        }
+    }
+
+    /// Installs the cross-chain bridge configuration, enabling [`Self::lock`]
+    /// and [`Self::release`].
+    pub fn install_bridge(&mut self, config: BridgeConfig) {
+        self.bridge = Some(config);
+    }
+
+    /// Derives the next deterministic token id from the collection name and a
+    /// monotonically increasing index.
+    fn next_token_id(&mut self) -> Hash {
+        let mut preimage = self.name.as_bytes().to_vec();
+        preimage.extend_from_slice(&self.next_index.to_le_bytes());
+        self.next_index += 1;
+        Hash::sha256(&preimage)
+    }
+
+    /// Mints `metadata` into the collection, crediting the metadata creator as
+    /// the initial owner. Returns the freshly assigned token id.
+    pub fn mint(
+        &mut self,
+        _ctx: &mut TxContext,
+        mut metadata: NftMetadata,
+        royalty_config: Option<RoyaltyConfig>,
+    ) -> Result<Hash, NftError> {
+        let token_id = self.next_token_id();
+        metadata.token_id = token_id;
+        let owner = metadata.creator.clone();
+
+        self.set_owner(token_id, owner);
+        if let Some(royalty) = royalty_config {
+            self.royalties.insert(token_id, royalty);
+        }
+        self.metadata.insert(token_id, metadata);
+        Ok(token_id)
+    }
+
+    /// Transfers `token_id` from `from` to `to`, splitting any sale proceeds
+    /// into royalties and the program fee.
+    pub fn transfer(
+        &mut self,
+        _ctx: &mut TxContext,
+        token_id: Hash,
+        from: &Address,
+        to: &Address,
+        sale_price: Option<Amount>,
+    ) -> Result<TransferOutcome, NftError> {
+        let current = self.token_owners.get(&token_id).ok_or(NftError::TokenNotFound)?;
+        if current != from {
+            return Err(NftError::Unauthorized);
+        }
+        if let Some(meta) = self.metadata.get(&token_id) {
+            if !meta.transferable {
+                return Err(NftError::NotTransferable);
+            }
+        }
+
+        let (royalties, program_fee) = match sale_price {
+            Some(price) => (self.compute_royalties(&token_id, price), Some(self.program_fee(price))),
+            None => (None, None),
+        };
+
+        self.set_owner(token_id, to.clone());
+
+        Ok(TransferOutcome {
+            token_id,
+            from: from.clone(),
+            to: to.clone(),
+            royalties,
+            program_fee,
+        })
+    }
+
+    /// Escrows `token_id` for transfer to `target_chain`, returning the payload
+    /// that guardians attest for release on the destination chain.
+    ///
+    /// A native token is reassigned to the program custody address; a wrapped
+    /// token (one that itself originated on another chain) is burned so supply
+    /// is conserved, and the payload carries the foreign origin coordinates so
+    /// the native token unlocks on its home chain.
+    pub fn lock(
+        &mut self,
+        ctx: &mut TxContext,
+        token_id: Hash,
+        target_chain: u16,
+        target_recipient: Address,
+    ) -> Result<TransferPayload, NftError> {
+        let (chain_id, collection_address, custody) = {
+            let bridge = self.bridge.as_ref().ok_or(NftError::BridgeNotConfigured)?;
+            (bridge.chain_id, bridge.collection_address.clone(), bridge.custody.clone())
+        };
+        let owner = self.token_owners.get(&token_id).ok_or(NftError::TokenNotFound)?;
+        if ctx.sender.as_ref() != Some(owner) {
+            return Err(NftError::Unauthorized);
+        }
+
+        let metadata = self.metadata.get(&token_id).ok_or(NftError::TokenNotFound)?.clone();
+        let royalty_config = self.royalties.get(&token_id).cloned();
+
+        let (origin_chain, origin_collection, origin_token_id) =
+            match self.wrapped_origins.get(&token_id) {
+                Some(origin) => origin.clone(),
+                None => (chain_id, collection_address, token_id),
+            };
+
+        let payload = TransferPayload {
+            origin_chain,
+            origin_collection,
+            origin_token_id,
+            metadata,
+            royalty_config,
+            target_chain,
+            target_recipient,
+        };
+
+        if self.wrapped_origins.contains_key(&token_id) {
+            // Wrapped token leaving this chain: burn it, keeping the registry
+            // mapping so a later re-mint reuses the same local id.
+            self.burn(token_id);
+        } else {
+            // Native token: move it into custody escrow.
+            self.set_owner(token_id, custody);
+        }
+
+        Ok(payload)
+    }
+
+    /// Releases a token against a guardian attestation, either unlocking a
+    /// native token held in escrow or minting/re-minting the wrapped token.
+    pub fn release(&mut self, _ctx: &mut TxContext, vaa: Vaa) -> Result<Hash, NftError> {
+        let (chain_id, custody, guardians) = {
+            let bridge = self.bridge.as_ref().ok_or(NftError::BridgeNotConfigured)?;
+            (bridge.chain_id, bridge.custody.clone(), bridge.guardians.clone())
+        };
+        let digest = vaa.payload.digest();
+
+        if self.consumed_attestations.contains(&digest) {
+            return Err(NftError::AttestationAlreadyConsumed);
+        }
+        // SECURITY: `quorum_reached` verifies only the simulated signatures
+        // described on [`GuardianSignature`], which carry no secret key and are
+        // forgeable by any caller. This provides no real custody protection and
+        // MUST be replaced with genuine guardian signature verification (e.g.
+        // Ed25519) before this gates a production bridge.
+        if !quorum_reached(&guardians, &vaa.signatures, &digest) {
+            return Err(NftError::InvalidAttestation);
+        }
+
+        let payload = vaa.payload;
+        let recipient = payload.target_recipient.clone();
+
+        if payload.origin_chain == chain_id {
+            // Native unlock: the token must actually be sitting in custody on
+            // this chain. Reject otherwise so a forged payload cannot conjure a
+            // token out of (or away from) an account that never escrowed one.
+            match self.token_owners.get(&payload.origin_token_id) {
+                Some(owner) if owner == &custody => {}
+                _ => return Err(NftError::NotInCustody),
+            }
+            self.consumed_attestations.insert(digest);
+            self.set_owner(payload.origin_token_id, recipient);
+            Ok(payload.origin_token_id)
+        } else {
+            self.consumed_attestations.insert(digest);
+            // Wrapped mint: reuse the registered local id if one exists so the
+            // same foreign token always maps to a single local token_id.
+            let key = (
+                payload.origin_chain,
+                payload.origin_collection.clone(),
+                payload.origin_token_id,
+            );
+            let local_id = match self.wrapped_registry.get(&key) {
+                Some(id) => *id,
+                None => {
+                    let id = self.next_token_id();
+                    self.wrapped_registry.insert(key.clone(), id);
+                    self.wrapped_origins.insert(id, key);
+                    id
+                }
+            };
+
+            let mut metadata = payload.metadata;
+            metadata.token_id = local_id;
+            self.set_owner(local_id, recipient);
+            self.metadata.insert(local_id, metadata);
+            if let Some(royalty) = payload.royalty_config {
+                self.royalties.insert(local_id, royalty);
+            }
+            Ok(local_id)
+        }
+    }
+
+    /// Grants `delegate` authority to consume `allowed_uses` uses of
+    /// `token_id`. Only the current owner may delegate.
+    pub fn approve_use_authority(
+        &mut self,
+        ctx: &mut TxContext,
+        token_id: Hash,
+        delegate: Address,
+        allowed_uses: u64,
+    ) -> Result<(), NftError> {
+        let owner = self.token_owners.get(&token_id).ok_or(NftError::TokenNotFound)?;
+        if ctx.sender.as_ref() != Some(owner) {
+            return Err(NftError::Unauthorized);
+        }
+        self.use_authorities.insert((token_id, delegate), allowed_uses);
+        Ok(())
+    }
+
+    /// Consumes `count` uses of `token_id`. The caller must be the owner or a
+    /// delegate with sufficient allowance; a delegate's allowance is debited
+    /// atomically. Under [`UseMethod::Burn`] the token is burned once its uses
+    /// reach zero.
+    pub fn utilize(
+        &mut self,
+        ctx: &mut TxContext,
+        token_id: Hash,
+        count: u64,
+    ) -> Result<(), NftError> {
+        let caller = ctx.sender.clone().ok_or(NftError::Unauthorized)?;
+        let owner = self.token_owners.get(&token_id).ok_or(NftError::TokenNotFound)?.clone();
+
+        // Check redeemability and availability before mutating anything, so an
+        // error path never leaves a delegate's allowance half-spent (the same
+        // check-before-mutate ordering `transfer` uses).
+        let remaining = self
+            .metadata
+            .get(&token_id)
+            .and_then(|meta| meta.uses.as_ref())
+            .ok_or(NftError::NotRedeemable)?
+            .remaining;
+        if remaining < count {
+            return Err(NftError::UsesExhausted);
+        }
+
+        // Authorize the caller, debiting a delegate's allowance atomically.
+        if caller != owner {
+            let key = (token_id, caller);
+            let allowance = self.use_authorities.get(&key).copied().unwrap_or(0);
+            if allowance < count {
+                return Err(NftError::UseAuthorityExceeded);
+            }
+            self.use_authorities.insert(key, allowance - count);
+        }
+
+        let uses = self
+            .metadata
+            .get_mut(&token_id)
+            .and_then(|meta| meta.uses.as_mut())
+            .expect("uses presence was checked above");
+        uses.remaining -= count;
+
+        let burn = uses.remaining == 0 && uses.use_method == UseMethod::Burn;
+        if burn {
+            self.burn(token_id);
+        }
+        Ok(())
+    }
+
+    /// Lists `token_id` for sale at `price`. Only the owner of a transferable
+    /// token may list it.
+    pub fn list(
+        &mut self,
+        ctx: &mut TxContext,
+        token_id: Hash,
+        price: Amount,
+        payment_token: Option<Address>,
+    ) -> Result<(), NftError> {
+        let seller = self.require_owner(ctx, &token_id)?;
+        if let Some(meta) = self.metadata.get(&token_id) {
+            if !meta.transferable {
+                return Err(NftError::NotTransferable);
+            }
+        }
+        self.listings.insert(token_id, Listing { seller, price, payment_token });
+        Ok(())
+    }
+
+    /// Cancels the listing for `token_id`. Only the seller may cancel.
+    pub fn cancel_listing(&mut self, ctx: &mut TxContext, token_id: Hash) -> Result<(), NftError> {
+        let listing = self.listings.get(&token_id).ok_or(NftError::ListingNotFound)?;
+        if ctx.sender.as_ref() != Some(&listing.seller) {
+            return Err(NftError::Unauthorized);
+        }
+        self.listings.remove(&token_id);
+        Ok(())
+    }
+
+    /// Buys a listed token at its asking price, splitting proceeds between
+    /// royalty recipients, the fee collector, and the seller.
+    pub fn buy(&mut self, ctx: &mut TxContext, token_id: Hash) -> Result<SaleSettlement, NftError> {
+        let buyer = ctx.sender.clone().ok_or(NftError::Unauthorized)?;
+        let listing = self.listings.get(&token_id).ok_or(NftError::ListingNotFound)?.clone();
+        // Re-verify the lister still owns the token: a stale listing must never
+        // move a token away from its current holder.
+        let owner = self.token_owners.get(&token_id).ok_or(NftError::TokenNotFound)?;
+        if owner != &listing.seller {
+            return Err(NftError::Unauthorized);
+        }
+        let settlement = self.settle_sale(token_id, listing.seller, buyer, listing.price, None)?;
+        self.clear_market_state(&token_id);
+        Ok(settlement)
+    }
+
+    /// Records a standing offer on `token_id`, pruning any already-expired
+    /// offers on the token so the offer book cannot grow without bound.
+    pub fn make_offer(
+        &mut self,
+        ctx: &mut TxContext,
+        token_id: Hash,
+        amount: Amount,
+        expiry: u64,
+    ) -> Result<(), NftError> {
+        let buyer = ctx.sender.clone().ok_or(NftError::Unauthorized)?;
+        let offers = self.offers.entry(token_id).or_default();
+        offers.retain(|offer| offer.expiry > ctx.block_time);
+        offers.push(Offer { buyer, amount, expiry });
+        Ok(())
+    }
+
+    /// Accepts the highest active offer from `buyer` on `token_id`, settling it
+    /// like a sale. Only the current owner of a transferable token may accept;
+    /// expired offers are ignored. The token's offer book is cleared on
+    /// settlement via [`Self::clear_market_state`].
+    pub fn accept_offer(
+        &mut self,
+        ctx: &mut TxContext,
+        token_id: Hash,
+        buyer: Address,
+    ) -> Result<SaleSettlement, NftError> {
+        let seller = self.require_owner(ctx, &token_id)?;
+        if let Some(meta) = self.metadata.get(&token_id) {
+            if !meta.transferable {
+                return Err(NftError::NotTransferable);
+            }
+        }
+
+        let amount = self
+            .offers
+            .get(&token_id)
+            .into_iter()
+            .flatten()
+            .filter(|offer| offer.buyer == buyer && offer.expiry > ctx.block_time)
+            .map(|offer| offer.amount)
+            .fold(None, |best, amount| match best {
+                Some(previous) if previous >= amount => Some(previous),
+                _ => Some(amount),
+            })
+            .ok_or(NftError::OfferNotFound)?;
+
+        self.settle_sale(token_id, seller, buyer, amount, None)
+    }
+
+    /// Opens an English auction for `token_id`. Only the owner of a
+    /// transferable token may open one.
+    pub fn create_auction(
+        &mut self,
+        ctx: &mut TxContext,
+        token_id: Hash,
+        reserve: Amount,
+        end_time: u64,
+    ) -> Result<(), NftError> {
+        let seller = self.require_owner(ctx, &token_id)?;
+        if let Some(meta) = self.metadata.get(&token_id) {
+            if !meta.transferable {
+                return Err(NftError::NotTransferable);
+            }
+        }
+
+        // Escrow the token for the duration of the auction, the same way
+        // `lock_for_swap` does, so the seller cannot move it out from under a
+        // live auction. `settle_auction` returns it to the seller or the winner.
+        self.clear_market_state(&token_id);
+        if let Some(tokens) = self.owner_index.get_mut(&seller) {
+            tokens.retain(|id| id != &token_id);
+        }
+        self.token_owners.remove(&token_id);
+
+        self.auctions.insert(
+            token_id,
+            Auction { seller, reserve, end_time, highest_bid: None },
+        );
+        Ok(())
+    }
+
+    /// Places a bid on an open auction, escrowing the bid and refunding the
+    /// previous top bidder. The bid must exceed both the reserve and the
+    /// current highest bid.
+    pub fn place_bid(
+        &mut self,
+        ctx: &mut TxContext,
+        token_id: Hash,
+        amount: Amount,
+    ) -> Result<Option<(Address, Amount)>, NftError> {
+        let bidder = ctx.sender.clone().ok_or(NftError::Unauthorized)?;
+        let auction = self.auctions.get_mut(&token_id).ok_or(NftError::ListingNotFound)?;
+
+        // Bidding closes at the end time; a later bid must not be accepted and
+        // then paid out by `settle_auction`.
+        if ctx.block_time >= auction.end_time {
+            return Err(NftError::AuctionClosed);
+        }
+
+        let floor = match &auction.highest_bid {
+            Some(bid) => bid.amount,
+            None => auction.reserve,
+        };
+        if amount <= floor {
+            return Err(NftError::BidTooLow);
+        }
+
+        let refund = auction
+            .highest_bid
+            .replace(Bid { bidder, amount })
+            .map(|previous| (previous.bidder, previous.amount));
+        Ok(refund)
+    }
+
+    /// Settles a closed auction. Transfers the token to the top bidder and
+    /// splits proceeds when the reserve was met; otherwise returns the escrowed
+    /// token to the seller. Either way the auction is cleared. Errors if the
+    /// auction has not yet ended.
+    pub fn settle_auction(
+        &mut self,
+        ctx: &mut TxContext,
+        token_id: Hash,
+    ) -> Result<Option<SaleSettlement>, NftError> {
+        let auction = self.auctions.get(&token_id).ok_or(NftError::ListingNotFound)?.clone();
+        if ctx.block_time < auction.end_time {
+            return Err(NftError::AuctionNotEnded);
+        }
+
+        // The token is held in escrow, so it moves either to the winning bidder
+        // or back to the seller. Superseded bidders were already refunded by
+        // `place_bid`, so the winner's bid is the only outstanding escrow and it
+        // becomes the sale proceeds; there is nothing left to refund here.
+        let settlement = match auction.highest_bid {
+            // `place_bid` enforces that every bid exceeds the reserve, so this
+            // guard is defense-in-depth against a mis-set reserve.
+            Some(bid) if bid.amount >= auction.reserve => Some(self.settle_sale(
+                token_id,
+                auction.seller,
+                bid.bidder,
+                bid.amount,
+                None,
+            )?),
+            // No qualifying bid: return the escrowed token to the seller.
+            _ => {
+                self.set_owner(token_id, auction.seller);
+                None
+            }
+        };
+        self.clear_market_state(&token_id);
+        Ok(settlement)
+    }
+
+    /// Returns the current owner of `token_id` if `ctx.sender` is that owner.
+    fn require_owner(&self, ctx: &TxContext, token_id: &Hash) -> Result<Address, NftError> {
+        let owner = self.token_owners.get(token_id).ok_or(NftError::TokenNotFound)?;
+        if ctx.sender.as_ref() != Some(owner) {
+            return Err(NftError::Unauthorized);
+        }
+        Ok(owner.clone())
+    }
+
+    /// Splits `price` into royalties, program fee, and seller proceeds, then
+    /// transfers ownership to `buyer`.
+    fn settle_sale(
+        &mut self,
+        token_id: Hash,
+        seller: Address,
+        buyer: Address,
+        price: Amount,
+        refund: Option<(Address, Amount)>,
+    ) -> Result<SaleSettlement, NftError> {
+        let royalties = self.compute_royalties(&token_id, price);
+        let program_fee = self.program_fee(price);
+
+        let mut withheld = program_fee;
+        if let Some(payout) = &royalties {
+            withheld += payout.total;
+        }
+        // Royalty basis points are unbounded, so the withheld total can exceed
+        // the price; reject rather than underflow or over-pay recipients.
+        let seller_proceeds = price.checked_sub(withheld).ok_or(NftError::RoyaltiesExceedPrice)?;
+
+        self.set_owner(token_id, buyer.clone());
+
+        Ok(SaleSettlement {
+            token_id,
+            seller,
+            buyer,
+            price,
+            royalties,
+            program_fee: Some(program_fee),
+            seller_proceeds,
+            refund,
+        })
+    }
+
+    /// Clears any listing, auction, or standing offers associated with
+    /// `token_id` once it has been sold or cancelled.
+    fn clear_market_state(&mut self, token_id: &Hash) {
+        self.listings.remove(token_id);
+        self.auctions.remove(token_id);
+        self.offers.remove(token_id);
+    }
+
+    /// Total number of tokens currently held in the collection.
+    pub fn total_supply(&self) -> u64 {
+        self.token_list.len() as u64
+    }
+
+    /// Returns a page of tokens in mint order, skipping `start` and returning
+    /// at most `limit` entries.
+    pub fn tokens(&self, start: usize, limit: usize) -> Vec<NftToken> {
+        self.page(&self.token_list, start, limit)
+    }
+
+    /// Returns a page of the tokens held by `owner`.
+    pub fn tokens_for_owner(&self, owner: &Address, start: usize, limit: usize) -> Vec<NftToken> {
+        match self.owner_index.get(owner) {
+            Some(tokens) => self.page(tokens, start, limit),
+            None => Vec::new(),
+        }
+    }
+
+    /// Number of tokens held by `owner`.
+    pub fn supply_for_owner(&self, owner: &Address) -> u64 {
+        self.owner_index.get(owner).map_or(0, |tokens| tokens.len() as u64)
+    }
+
+    /// Materializes a page of [`NftToken`] views from a slice of token ids.
+    fn page(&self, ids: &[Hash], start: usize, limit: usize) -> Vec<NftToken> {
+        ids.iter()
+            .skip(start)
+            .take(limit)
+            .filter_map(|id| self.token_view(id))
+            .collect()
+    }
+
+    /// Builds a light-weight [`NftToken`] view for `token_id`.
+    fn token_view(&self, token_id: &Hash) -> Option<NftToken> {
+        self.metadata.get(token_id).map(|meta| NftToken {
+            token_id: *token_id,
+            name: meta.name.clone(),
+            token_type: TokenType::Nft,
+        })
+    }
+
+    /// Records ownership of `token_id` by `new_owner`, keeping the enumeration
+    /// indexes in sync. A token not previously owned is appended to the
+    /// collection-wide list.
+    fn set_owner(&mut self, token_id: Hash, new_owner: Address) {
+        match self.token_owners.get(&token_id) {
+            Some(previous) => {
+                if let Some(tokens) = self.owner_index.get_mut(previous) {
+                    tokens.retain(|id| id != &token_id);
+                }
+            }
+            // A token with no current public owner (freshly minted, or held in
+            // swap/shielded escrow) is enumerable only once.
+            None => {
+                if !self.token_list.contains(&token_id) {
+                    self.token_list.push(token_id);
+                }
+            }
+        }
+        self.owner_index.entry(new_owner.clone()).or_default().push(token_id);
+        self.token_owners.insert(token_id, new_owner);
+        // Any listing or auction refers to the previous owner, so it no longer
+        // reflects a standing sale offer once the token changes hands.
+        self.clear_market_state(&token_id);
+    }
+
+    /// Removes `token_id` from the collection, keeping the enumeration indexes
+    /// and metadata maps in sync.
+    fn burn(&mut self, token_id: Hash) {
+        if let Some(previous) = self.token_owners.remove(&token_id) {
+            if let Some(tokens) = self.owner_index.get_mut(&previous) {
+                tokens.retain(|id| id != &token_id);
+            }
+        }
+        self.token_list.retain(|id| id != &token_id);
+        self.metadata.remove(&token_id);
+        self.royalties.remove(&token_id);
+        self.clear_market_state(&token_id);
+    }
+
+    /// Escrows `token_id` into a hash-timelocked swap redeemable by revealing
+    /// a preimage of `hashlock` before `timelock`. Only the owner may lock.
+    pub fn lock_for_swap(
+        &mut self,
+        ctx: &mut TxContext,
+        token_id: Hash,
+        hashlock: Hash,
+        timelock: u64,
+        counterparty: Address,
+    ) -> Result<(), NftError> {
+        let originator = self.require_owner(ctx, &token_id)?;
+        if let Some(meta) = self.metadata.get(&token_id) {
+            if !meta.transferable {
+                return Err(NftError::NotTransferable);
+            }
+        }
+
+        // Remove the public owner while the token sits in escrow.
+        if let Some(tokens) = self.owner_index.get_mut(&originator) {
+            tokens.retain(|id| id != &token_id);
+        }
+        self.token_owners.remove(&token_id);
+        self.clear_market_state(&token_id);
+
+        self.swaps.insert(
+            token_id,
+            SwapState { originator, counterparty, hashlock, timelock },
+        );
+        Ok(())
+    }
+
+    /// Claims an escrowed token by revealing the swap preimage, transferring
+    /// the token to the counterparty and recording the revealed secret so it
+    /// can be reused to claim the paired asset. Succeeds only before the
+    /// timelock expires.
+    pub fn claim_swap(
+        &mut self,
+        ctx: &mut TxContext,
+        token_id: Hash,
+        preimage: Vec<u8>,
+    ) -> Result<(), NftError> {
+        let swap = self.swaps.get(&token_id).ok_or(NftError::TokenNotFound)?.clone();
+        if ctx.block_time >= swap.timelock {
+            return Err(NftError::SwapExpired);
+        }
+        if Hash::sha256(&preimage) != swap.hashlock {
+            return Err(NftError::InvalidPreimage);
+        }
+
+        self.swaps.remove(&token_id);
+        self.revealed_secrets.insert(swap.hashlock, preimage);
+        self.set_owner(token_id, swap.counterparty);
+        Ok(())
+    }
+
+    /// Refunds an escrowed token to its originator once the timelock has
+    /// expired.
+    pub fn refund_swap(&mut self, ctx: &mut TxContext, token_id: Hash) -> Result<(), NftError> {
+        let swap = self.swaps.get(&token_id).ok_or(NftError::TokenNotFound)?.clone();
+        if ctx.block_time < swap.timelock {
+            return Err(NftError::SwapNotExpired);
+        }
+
+        self.swaps.remove(&token_id);
+        self.set_owner(token_id, swap.originator);
+        Ok(())
+    }
+
+    /// Performs a shielded transfer of `token_id`. Rather than writing a new
+    /// owner into the public map, it records `commitment` into the note tree
+    /// and publishes `nullifier`, after checking `proof` against
+    /// [`shielded_binding`] and rejecting a reused nullifier.
+    pub fn shielded_transfer(
+        &mut self,
+        ctx: &mut TxContext,
+        token_id: Hash,
+        commitment: Hash,
+        nullifier: Hash,
+        proof: Vec<u8>,
+    ) -> Result<(), NftError> {
+        // Shielding a publicly owned token requires the owner's authority;
+        // without this anyone could freeze the public ownership of a token they
+        // do not hold. A token already in the pool has no public owner, so it is
+        // authorized by its note proof alone.
+        match self.token_owners.get(&token_id) {
+            Some(owner) => {
+                if ctx.sender.as_ref() != Some(owner) {
+                    return Err(NftError::Unauthorized);
+                }
+            }
+            None => {
+                if !self.shielded.contains(&token_id) {
+                    return Err(NftError::TokenNotFound);
+                }
+            }
+        }
+        // SECURITY: `verify_shielded_proof` checks the forgeable placeholder
+        // described on [`shielded_binding`], not a real zero-knowledge proof, so
+        // it attests no knowledge of a note opening. The owner gate above covers
+        // the first (public -> shielded) hop, but shielded -> shielded spends are
+        // authenticated by this value alone. Non-production; MUST be replaced
+        // with a proof bound to a spending key before live use.
+        if !verify_shielded_proof(&token_id, &commitment, &nullifier, &proof) {
+            return Err(NftError::InvalidProof);
+        }
+        if self.nullifiers.contains(&nullifier) {
+            return Err(NftError::NullifierAlreadyUsed);
+        }
+
+        self.nullifiers.insert(nullifier);
+        self.note_tree.append(commitment);
+
+        // Move the token into the shielded pool: drop the public owner while
+        // keeping the token enumerable.
+        if let Some(previous) = self.token_owners.remove(&token_id) {
+            if let Some(tokens) = self.owner_index.get_mut(&previous) {
+                tokens.retain(|id| id != &token_id);
+            }
+        }
+        self.clear_market_state(&token_id);
+        self.shielded.insert(token_id);
+        Ok(())
+    }
+
+    /// Whether `token_id` is currently held in the shielded pool.
+    pub fn is_shielded(&self, token_id: &Hash) -> bool {
+        self.shielded.contains(token_id)
+    }
+
+    /// The publicly observable owner of `token_id`. Shielded tokens have no
+    /// public owner; ownership is proven client-side.
+    pub fn public_owner(&self, token_id: &Hash) -> Option<Address> {
+        if self.shielded.contains(token_id) {
+            return None;
+        }
+        self.token_owners.get(token_id).cloned()
+    }
+
+    /// The publicly observable visibility level of `token_id`.
+    pub fn visibility(&self, token_id: &Hash) -> VisibilityLevel {
+        if self.shielded.contains(token_id) {
+            return VisibilityLevel::Private;
+        }
+        self.metadata
+            .get(token_id)
+            .and_then(|meta| meta.privacy_config.as_ref())
+            .map_or(VisibilityLevel::Public, |privacy| privacy.visibility)
+    }
+
+    /// Computes the royalty split owed on a sale at `price`.
+    fn compute_royalties(&self, token_id: &Hash, price: Amount) -> Option<RoyaltyPayout> {
+        let config = self.royalties.get(token_id)?;
+        let mut payments = Vec::new();
+
+        let creator_cut = basis_points(price, config.royalty_percentage);
+        if !creator_cut.is_zero() {
+            payments.push((config.creator.clone(), creator_cut));
+        }
+        for (recipient, bps) in &config.secondary_recipients {
+            let cut = basis_points(price, *bps);
+            if !cut.is_zero() {
+                payments.push((recipient.clone(), cut));
+            }
+        }
+
+        let total = payments.iter().fold(Amount::zero(), |acc, (_, amount)| acc + *amount);
+        Some(RoyaltyPayout { total, payments })
+    }
+
+    /// Computes the program fee owed on a sale at `price`.
+    fn program_fee(&self, price: Amount) -> Amount {
+        basis_points(price, self.fee_basis_points)
+    }
+}
+
+/// Returns `bps` basis points of `amount`.
+fn basis_points(amount: Amount, bps: u16) -> Amount {
+    (amount * bps as u64) / 10_000u64
+}
+
+/// Hashes two nodes into their Merkle parent.
+fn hash_pair(left: &Hash, right: &Hash) -> Hash {
+    let mut preimage = left.as_ref().to_vec();
+    preimage.extend_from_slice(right.as_ref());
+    Hash::sha256(&preimage)
+}
+
+/// The canonical binding a valid shielded-transfer proof must reproduce over
+/// the public inputs `(token_id, commitment, nullifier)`.
+///
+/// # Security
+///
+/// This is an **insecure placeholder** for a real zero-knowledge proof: it is a
+/// pure function of public inputs with no spender secret, so any caller can
+/// recompute a valid `proof`. It does not attest that the spender knows the
+/// opening of an unspent commitment. A production shielded pool must replace
+/// this with a proof bound to a spending key; until then
+/// [`NftCollection::shielded_transfer`] additionally gates on public owner
+/// authorization.
+pub fn shielded_binding(token_id: &Hash, commitment: &Hash, nullifier: &Hash) -> Vec<u8> {
+    let mut preimage = token_id.as_ref().to_vec();
+    preimage.extend_from_slice(commitment.as_ref());
+    preimage.extend_from_slice(nullifier.as_ref());
+    Hash::sha256(&preimage).as_ref().to_vec()
+}
+
+/// Checks that `proof` equals [`shielded_binding`] over `(token_id,
+/// commitment, nullifier)`. This does not establish that the spender knows an
+/// opening of `commitment`; see the security note on [`shielded_binding`].
+fn verify_shielded_proof(
+    token_id: &Hash,
+    commitment: &Hash,
+    nullifier: &Hash,
+    proof: &[u8],
+) -> bool {
+    proof == shielded_binding(token_id, commitment, nullifier).as_slice()
+}
+
+/// Returns `true` when `signatures` contains at least `guardians.quorum`
+/// distinct, valid signatures from members of the guardian set over `digest`.
+fn quorum_reached(guardians: &GuardianSet, signatures: &[GuardianSignature], digest: &Hash) -> bool {
+    let mut seen: HashSet<&Address> = HashSet::new();
+    for sig in signatures {
+        if guardians.guardians.contains(&sig.guardian)
+            && sig.verifies(digest)
+            && seen.insert(&sig.guardian)
+            && seen.len() >= guardians.quorum
+        {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests;
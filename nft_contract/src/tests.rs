@@ -1,6 +1,8 @@
 // ==========================
 // On-chain Program Unit Tests
 // ==========================
+use crate::*;
+
 #[cfg(test)]
 mod program_tests {
     use super::*;
@@ -37,8 +39,9 @@ mod program_tests {
                 encryption_key: None,
                 visibility: VisibilityLevel::Public,
             }),
+            uses: None,
         };
-        
+
         // Royalty configuration.
         let royalty_config = RoyaltyConfig {
             creator: creator.clone(),
@@ -96,6 +99,7 @@ mod program_tests {
             attributes: HashMap::new(),
             transferable: true,
             privacy_config: None,
+            uses: None,
         };
         
         let token_id = collection.mint(
@@ -114,4 +118,401 @@ mod program_tests {
         );
         assert!(matches!(result, Err(NftError::Unauthorized)));
     }
+
+    #[test]
+    fn test_bridge_lock_and_release() {
+        let creator = Address::from_str("namada1creator").unwrap();
+        let custody = Address::from_str("namada1custody").unwrap();
+        let fee_collector = Address::from_str("namada1feecollector").unwrap();
+        let recipient = Address::from_str("namada1recipient").unwrap();
+        let guardian = Address::from_str("namada1guardian").unwrap();
+
+        let mut collection =
+            NftCollection::new("Test Collection".to_string(), fee_collector, 10);
+        collection.install_bridge(BridgeConfig {
+            chain_id: 1,
+            collection_address: Address::from_str("namada1collection").unwrap(),
+            custody: custody.clone(),
+            guardians: GuardianSet { guardians: vec![guardian.clone()], quorum: 1 },
+        });
+
+        let metadata = NftMetadata {
+            token_id: Hash::default(),
+            name: "Bridged NFT".to_string(),
+            description: None,
+            uri: None,
+            creator: creator.clone(),
+            attributes: HashMap::new(),
+            transferable: true,
+            privacy_config: None,
+            uses: None,
+        };
+        let token_id = collection
+            .mint(&mut TxContext::default(), metadata, None)
+            .expect("Minting should succeed");
+
+        // Lock escrows the token into custody.
+        let ctx = &mut TxContext { sender: Some(creator.clone()), ..Default::default() };
+        let payload = collection
+            .lock(ctx, token_id, 2, recipient.clone())
+            .expect("Lock should succeed");
+        assert_eq!(*collection.token_owners.get(&token_id).unwrap(), custody);
+
+        // A quorum attestation releases the native token back to a recipient.
+        let digest = payload.digest();
+        let vaa = Vaa {
+            payload,
+            signatures: vec![GuardianSignature::attest(&guardian, &digest)],
+        };
+        let released = collection
+            .release(&mut TxContext::default(), vaa.clone())
+            .expect("Release should succeed");
+        assert_eq!(released, token_id);
+        assert_eq!(*collection.token_owners.get(&token_id).unwrap(), recipient);
+
+        // Replaying the same attestation is rejected.
+        let replay = collection.release(&mut TxContext::default(), vaa);
+        assert!(matches!(replay, Err(NftError::AttestationAlreadyConsumed)));
+    }
+
+    fn redeemable(creator: &Address, method: UseMethod, total: u64) -> NftMetadata {
+        NftMetadata {
+            token_id: Hash::default(),
+            name: "Event Pass".to_string(),
+            description: None,
+            uri: None,
+            creator: creator.clone(),
+            attributes: HashMap::new(),
+            transferable: true,
+            privacy_config: None,
+            uses: Some(Uses { use_method: method, total, remaining: total }),
+        }
+    }
+
+    #[test]
+    fn test_delegate_redemption() {
+        let holder = Address::from_str("namada1holder").unwrap();
+        let venue = Address::from_str("namada1venue").unwrap();
+        let fee_collector = Address::from_str("namada1feecollector").unwrap();
+
+        let mut collection =
+            NftCollection::new("Passes".to_string(), fee_collector, 10);
+        let token_id = collection
+            .mint(&mut TxContext::default(), redeemable(&holder, UseMethod::Multiple, 3), None)
+            .expect("Minting should succeed");
+
+        // The holder delegates two uses to the venue address.
+        let holder_ctx = &mut TxContext { sender: Some(holder.clone()), ..Default::default() };
+        collection
+            .approve_use_authority(holder_ctx, token_id, venue.clone(), 2)
+            .expect("Delegation should succeed");
+
+        // The venue redeems on the holder's behalf, debiting its allowance.
+        let venue_ctx = &mut TxContext { sender: Some(venue.clone()), ..Default::default() };
+        collection.utilize(venue_ctx, token_id, 1).expect("Delegate use should succeed");
+        assert_eq!(*collection.use_authorities.get(&(token_id, venue.clone())).unwrap(), 1);
+        assert_eq!(collection.metadata.get(&token_id).unwrap().uses.unwrap().remaining, 2);
+
+        // Exceeding the remaining allowance is rejected.
+        let over = collection.utilize(venue_ctx, token_id, 2);
+        assert!(matches!(over, Err(NftError::UseAuthorityExceeded)));
+    }
+
+    #[test]
+    fn test_burn_on_final_use() {
+        let holder = Address::from_str("namada1holder").unwrap();
+        let fee_collector = Address::from_str("namada1feecollector").unwrap();
+
+        let mut collection =
+            NftCollection::new("Tickets".to_string(), fee_collector, 10);
+        let token_id = collection
+            .mint(&mut TxContext::default(), redeemable(&holder, UseMethod::Burn, 1), None)
+            .expect("Minting should succeed");
+
+        let holder_ctx = &mut TxContext { sender: Some(holder.clone()), ..Default::default() };
+        collection.utilize(holder_ctx, token_id, 1).expect("Final use should succeed");
+
+        // The token is burned once its last use is consumed.
+        assert!(collection.token_owners.get(&token_id).is_none());
+        assert!(collection.metadata.get(&token_id).is_none());
+        let exhausted = collection.utilize(holder_ctx, token_id, 1);
+        assert!(matches!(exhausted, Err(NftError::TokenNotFound)));
+    }
+
+    fn collectible(creator: &Address) -> NftMetadata {
+        NftMetadata {
+            token_id: Hash::default(),
+            name: "Collectible".to_string(),
+            description: None,
+            uri: None,
+            creator: creator.clone(),
+            attributes: HashMap::new(),
+            transferable: true,
+            privacy_config: None,
+            uses: None,
+        }
+    }
+
+    #[test]
+    fn test_marketplace_list_and_buy() {
+        let creator = Address::from_str("namada1creator").unwrap();
+        let buyer = Address::from_str("namada1buyer").unwrap();
+        let fee_collector = Address::from_str("namada1feecollector").unwrap();
+
+        let mut collection =
+            NftCollection::new("Market".to_string(), fee_collector, 100); // 1% fee
+        let royalty = RoyaltyConfig {
+            creator: creator.clone(),
+            royalty_percentage: 500, // 5%
+            secondary_recipients: vec![],
+            royalty_token: None,
+        };
+        let token_id = collection
+            .mint(&mut TxContext::default(), collectible(&creator), Some(royalty))
+            .expect("Minting should succeed");
+
+        let seller_ctx = &mut TxContext { sender: Some(creator.clone()), ..Default::default() };
+        collection
+            .list(seller_ctx, token_id, Amount::from(1_000_000u64), None)
+            .expect("Listing should succeed");
+
+        let buyer_ctx = &mut TxContext { sender: Some(buyer.clone()), ..Default::default() };
+        let settlement = collection.buy(buyer_ctx, token_id).expect("Buy should succeed");
+
+        assert_eq!(*collection.token_owners.get(&token_id).unwrap(), buyer);
+        assert!(collection.listings.get(&token_id).is_none());
+        assert_eq!(settlement.program_fee, Some(Amount::from(10_000u64))); // 1%
+        assert_eq!(settlement.royalties.unwrap().total, Amount::from(50_000u64)); // 5%
+        assert_eq!(settlement.seller_proceeds, Amount::from(940_000u64));
+    }
+
+    #[test]
+    fn test_offer_accept_and_expiry() {
+        let creator = Address::from_str("namada1creator").unwrap();
+        let buyer = Address::from_str("namada1buyer").unwrap();
+        let fee_collector = Address::from_str("namada1feecollector").unwrap();
+
+        let mut collection =
+            NftCollection::new("Offers".to_string(), fee_collector, 100); // 1% fee
+        let token_id = collection
+            .mint(&mut TxContext::default(), collectible(&creator), None)
+            .expect("Minting should succeed");
+
+        // An expired offer cannot be accepted.
+        let offer_ctx = &mut TxContext { sender: Some(buyer.clone()), block_time: 10, ..Default::default() };
+        collection
+            .make_offer(offer_ctx, token_id, Amount::from(500u64), 50)
+            .expect("Offer should record");
+        let stale_ctx = &mut TxContext { sender: Some(creator.clone()), block_time: 100, ..Default::default() };
+        let expired = collection.accept_offer(stale_ctx, token_id, buyer.clone());
+        assert!(matches!(expired, Err(NftError::OfferNotFound)));
+
+        // A fresh, active offer settles like a sale and clears the offer book.
+        let fresh_ctx = &mut TxContext { sender: Some(buyer.clone()), block_time: 100, ..Default::default() };
+        collection
+            .make_offer(fresh_ctx, token_id, Amount::from(1_000_000u64), 1_000)
+            .expect("Offer should record");
+        let accept_ctx = &mut TxContext { sender: Some(creator.clone()), block_time: 200, ..Default::default() };
+        let settlement = collection
+            .accept_offer(accept_ctx, token_id, buyer.clone())
+            .expect("Accept should succeed");
+        assert_eq!(settlement.buyer, buyer);
+        assert_eq!(settlement.program_fee, Some(Amount::from(10_000u64))); // 1%
+        assert_eq!(*collection.token_owners.get(&token_id).unwrap(), buyer);
+        assert!(collection.offers.get(&token_id).is_none());
+    }
+
+    #[test]
+    fn test_auction_bids_and_settlement() {
+        let creator = Address::from_str("namada1creator").unwrap();
+        let bidder_one = Address::from_str("namada1bidderone").unwrap();
+        let bidder_two = Address::from_str("namada1biddertwo").unwrap();
+        let fee_collector = Address::from_str("namada1feecollector").unwrap();
+
+        let mut collection =
+            NftCollection::new("Auctions".to_string(), fee_collector, 100);
+        let token_id = collection
+            .mint(&mut TxContext::default(), collectible(&creator), None)
+            .expect("Minting should succeed");
+
+        let seller_ctx = &mut TxContext { sender: Some(creator.clone()), ..Default::default() };
+        collection
+            .create_auction(seller_ctx, token_id, Amount::from(100u64), 1_000)
+            .expect("Auction creation should succeed");
+        // The token is escrowed for the auction's duration.
+        assert!(collection.token_owners.get(&token_id).is_none());
+
+        // First bid clears the reserve; a second, higher bid refunds the first.
+        let one_ctx = &mut TxContext { sender: Some(bidder_one.clone()), ..Default::default() };
+        assert!(collection.place_bid(one_ctx, token_id, Amount::from(200u64)).unwrap().is_none());
+        let low = collection.place_bid(one_ctx, token_id, Amount::from(150u64));
+        assert!(matches!(low, Err(NftError::BidTooLow)));
+
+        let two_ctx = &mut TxContext { sender: Some(bidder_two.clone()), ..Default::default() };
+        let refund = collection.place_bid(two_ctx, token_id, Amount::from(300u64)).unwrap();
+        assert_eq!(refund, Some((bidder_one.clone(), Amount::from(200u64))));
+
+        // Bids at or after the end time are rejected, not paid out later.
+        let late = collection.place_bid(
+            &mut TxContext { sender: Some(bidder_one.clone()), block_time: 1_000, ..Default::default() },
+            token_id,
+            Amount::from(400u64),
+        );
+        assert!(matches!(late, Err(NftError::AuctionClosed)));
+
+        // Settling before the end time is rejected.
+        let early = collection.settle_auction(
+            &mut TxContext { block_time: 500, ..Default::default() },
+            token_id,
+        );
+        assert!(matches!(early, Err(NftError::AuctionNotEnded)));
+
+        let settlement = collection
+            .settle_auction(&mut TxContext { block_time: 1_000, ..Default::default() }, token_id)
+            .expect("Settlement should succeed")
+            .expect("Reserve met, so the token sells");
+        assert_eq!(settlement.buyer, bidder_two);
+        assert_eq!(*collection.token_owners.get(&token_id).unwrap(), bidder_two);
+        assert!(collection.auctions.get(&token_id).is_none());
+    }
+
+    #[test]
+    fn test_enumeration() {
+        let creator = Address::from_str("namada1creator").unwrap();
+        let recipient = Address::from_str("namada1recipient").unwrap();
+        let fee_collector = Address::from_str("namada1feecollector").unwrap();
+
+        let mut collection =
+            NftCollection::new("Enumerable".to_string(), fee_collector, 10);
+        let first = collection
+            .mint(&mut TxContext::default(), collectible(&creator), None)
+            .expect("Minting should succeed");
+        let _second = collection
+            .mint(&mut TxContext::default(), collectible(&creator), None)
+            .expect("Minting should succeed");
+
+        assert_eq!(collection.total_supply(), 2);
+        assert_eq!(collection.supply_for_owner(&creator), 2);
+        assert_eq!(collection.tokens(0, 10).len(), 2);
+        assert_eq!(collection.tokens(1, 10).len(), 1);
+
+        // Transferring keeps the reverse index in sync for both owners.
+        collection
+            .transfer(&mut TxContext::default(), first, &creator, &recipient, None)
+            .expect("Transfer should succeed");
+        assert_eq!(collection.supply_for_owner(&creator), 1);
+        assert_eq!(collection.supply_for_owner(&recipient), 1);
+        let recipient_tokens = collection.tokens_for_owner(&recipient, 0, 10);
+        assert_eq!(recipient_tokens.len(), 1);
+        assert_eq!(recipient_tokens[0].token_id, first);
+    }
+
+    #[test]
+    fn test_shielded_transfer_double_spend() {
+        let creator = Address::from_str("namada1creator").unwrap();
+        let fee_collector = Address::from_str("namada1feecollector").unwrap();
+
+        let mut collection =
+            NftCollection::new("Shielded".to_string(), fee_collector, 10);
+        let token_id = collection
+            .mint(&mut TxContext::default(), collectible(&creator), None)
+            .expect("Minting should succeed");
+
+        // A single note: its nullifier and commitment, with a binding proof.
+        let commitment = Hash::from([7u8; 32]);
+        let nullifier = Hash::from([9u8; 32]);
+        let proof = crate::shielded_binding(&token_id, &commitment, &nullifier);
+
+        // Only the current owner may shield a publicly owned token.
+        let owner_ctx = &mut TxContext { sender: Some(creator.clone()), ..Default::default() };
+        collection
+            .shielded_transfer(owner_ctx, token_id, commitment, nullifier, proof.clone())
+            .expect("Shielded transfer should succeed");
+
+        // The token is now shielded: no public owner, visibility is Private.
+        assert!(collection.is_shielded(&token_id));
+        assert!(collection.public_owner(&token_id).is_none());
+        assert_eq!(collection.visibility(&token_id), VisibilityLevel::Private);
+
+        // Re-spending the same note (same nullifier) is rejected.
+        let replay = collection.shielded_transfer(
+            owner_ctx,
+            token_id,
+            commitment,
+            nullifier,
+            proof,
+        );
+        assert!(matches!(replay, Err(NftError::NullifierAlreadyUsed)));
+    }
+
+    #[test]
+    fn test_swap_claim_before_timeout() {
+        let originator = Address::from_str("namada1originator").unwrap();
+        let counterparty = Address::from_str("namada1counterparty").unwrap();
+        let fee_collector = Address::from_str("namada1feecollector").unwrap();
+
+        let mut collection =
+            NftCollection::new("Swaps".to_string(), fee_collector, 10);
+        let token_id = collection
+            .mint(&mut TxContext::default(), collectible(&originator), None)
+            .expect("Minting should succeed");
+
+        let preimage = b"swap-secret".to_vec();
+        let hashlock = Hash::sha256(&preimage);
+
+        let lock_ctx = &mut TxContext { sender: Some(originator.clone()), ..Default::default() };
+        collection
+            .lock_for_swap(lock_ctx, token_id, hashlock, 1_000, counterparty.clone())
+            .expect("Lock should succeed");
+        assert!(collection.token_owners.get(&token_id).is_none());
+
+        // A wrong preimage is rejected.
+        let bad = collection.claim_swap(
+            &mut TxContext { block_time: 500, ..Default::default() },
+            token_id,
+            b"wrong".to_vec(),
+        );
+        assert!(matches!(bad, Err(NftError::InvalidPreimage)));
+
+        // Claiming before the timelock with the correct preimage succeeds and
+        // records the secret for the paired leg.
+        collection
+            .claim_swap(&mut TxContext { block_time: 500, ..Default::default() }, token_id, preimage.clone())
+            .expect("Claim should succeed");
+        assert_eq!(*collection.token_owners.get(&token_id).unwrap(), counterparty);
+        assert_eq!(collection.revealed_secrets.get(&hashlock).unwrap(), &preimage);
+    }
+
+    #[test]
+    fn test_swap_refund_after_timeout() {
+        let originator = Address::from_str("namada1originator").unwrap();
+        let counterparty = Address::from_str("namada1counterparty").unwrap();
+        let fee_collector = Address::from_str("namada1feecollector").unwrap();
+
+        let mut collection =
+            NftCollection::new("Swaps".to_string(), fee_collector, 10);
+        let token_id = collection
+            .mint(&mut TxContext::default(), collectible(&originator), None)
+            .expect("Minting should succeed");
+
+        let hashlock = Hash::sha256(b"another-secret");
+        let lock_ctx = &mut TxContext { sender: Some(originator.clone()), ..Default::default() };
+        collection
+            .lock_for_swap(lock_ctx, token_id, hashlock, 1_000, counterparty)
+            .expect("Lock should succeed");
+
+        // Refunding before expiry is rejected.
+        let early = collection.refund_swap(
+            &mut TxContext { block_time: 500, ..Default::default() },
+            token_id,
+        );
+        assert!(matches!(early, Err(NftError::SwapNotExpired)));
+
+        // After the timelock, the originator reclaims the token.
+        collection
+            .refund_swap(&mut TxContext { block_time: 1_000, ..Default::default() }, token_id)
+            .expect("Refund should succeed");
+        assert_eq!(*collection.token_owners.get(&token_id).unwrap(), originator);
+        assert!(collection.swaps.get(&token_id).is_none());
+    }
 }